@@ -0,0 +1,55 @@
+// benches/force_eval.rs
+//! Compares serial vs. rayon-parallel Barnes-Hut force evaluation at body
+//! counts large enough that the simulation used to stall (10k-100k).
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use gravitas::body::Body;
+use gravitas::quad_tree::{Boundary, QuadTree};
+use gravitas::simulation::generate_random_bodies;
+use rayon::prelude::*;
+
+fn build_tree(bodies: &[Body], boundary: Boundary) -> QuadTree {
+    let mut tree = QuadTree::new(boundary);
+    for body in bodies {
+        tree.insert(body.clone());
+    }
+    tree
+}
+
+fn bench_force_eval(c: &mut Criterion) {
+    let boundary = Boundary {
+        x_min: -1.0e6,
+        x_max: 1.0e6,
+        y_min: -1.0e6,
+        y_max: 1.0e6,
+    };
+    let theta = 0.5;
+    let eps = 1.0e3;
+
+    let mut group = c.benchmark_group("force_eval");
+    for &count in &[10_000usize, 100_000] {
+        let bodies = generate_random_bodies(count, &boundary);
+        let tree = build_tree(&bodies, boundary);
+
+        group.bench_with_input(BenchmarkId::new("serial", count), &count, |b, _| {
+            b.iter(|| {
+                bodies
+                    .iter()
+                    .map(|body| tree.calculate_force(body, theta, eps))
+                    .collect::<Vec<_>>()
+            });
+        });
+
+        group.bench_with_input(BenchmarkId::new("parallel", count), &count, |b, _| {
+            b.iter(|| {
+                bodies
+                    .par_iter()
+                    .map(|body| tree.calculate_force(body, theta, eps))
+                    .collect::<Vec<_>>()
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_force_eval);
+criterion_main!(benches);
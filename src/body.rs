@@ -1,6 +1,14 @@
 // body.rs
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
 #[derive(Clone)]
 pub struct Body {
+    /// Stable identity, assigned once at construction so bodies can be
+    /// looked up (e.g. during collision merging) without relying on
+    /// position equality, which two distinct bodies could share.
+    pub id: u64,
     pub position: (f64, f64),
     pub velocity: (f64, f64),
     pub acceleration: (f64, f64),
@@ -11,26 +19,73 @@ pub struct Body {
 impl Body {
     pub fn new(position: (f64, f64), velocity: (f64, f64), mass: f64, _radius: f64) -> Self {
         Self {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
             position,
             velocity,
             acceleration: (0.0, 0.0),
             mass,
-            _radius: 1.0,
+            _radius,
+        }
+    }
+
+    /// Advances velocity by half a kick, using the acceleration from before
+    /// (first call) or after (second call) the drift. Used by the
+    /// kick-drift-kick velocity-Verlet integrator in `Simulation::update`.
+    pub fn half_kick(&mut self, dt: f64) {
+        self.velocity.0 += 0.5 * self.acceleration.0 * dt;
+        self.velocity.1 += 0.5 * self.acceleration.1 * dt;
+    }
+
+    /// Advances position by a full step using the current velocity.
+    pub fn drift(&mut self, dt: f64) {
+        self.position.0 += self.velocity.0 * dt;
+        self.position.1 += self.velocity.1 * dt;
+    }
+
+    pub fn apply_force(&mut self, force: (f64, f64)) {
+        self.acceleration.0 = force.0 / self.mass;
+        self.acceleration.1 = force.1 / self.mass;
+    }
+}
+
+/// Three-dimensional counterpart to `Body`, used by `oct_tree`. Kept as a
+/// separate type rather than making `Body` generic over dimension, so 2D
+/// callers are unaffected.
+#[derive(Clone)]
+pub struct Body3 {
+    pub position: (f64, f64, f64),
+    pub velocity: (f64, f64, f64),
+    pub acceleration: (f64, f64, f64),
+    pub mass: f64,
+    pub _radius: f64,
+}
+
+impl Body3 {
+    pub fn new(position: (f64, f64, f64), velocity: (f64, f64, f64), mass: f64, _radius: f64) -> Self {
+        Self {
+            position,
+            velocity,
+            acceleration: (0.0, 0.0, 0.0),
+            mass,
+            _radius,
         }
     }
 
     pub fn update_position(&mut self, dt: f64) {
         self.position.0 += self.velocity.0 * dt + 0.5 * self.acceleration.0 * dt * dt;
         self.position.1 += self.velocity.1 * dt + 0.5 * self.acceleration.1 * dt * dt;
+        self.position.2 += self.velocity.2 * dt + 0.5 * self.acceleration.2 * dt * dt;
     }
 
     pub fn update_velocity(&mut self, dt: f64) {
         self.velocity.0 += self.acceleration.0 * dt;
         self.velocity.1 += self.acceleration.1 * dt;
+        self.velocity.2 += self.acceleration.2 * dt;
     }
 
-    pub fn apply_force(&mut self, force: (f64, f64)) {
+    pub fn apply_force(&mut self, force: (f64, f64, f64)) {
         self.acceleration.0 = force.0 / self.mass;
         self.acceleration.1 = force.1 / self.mass;
+        self.acceleration.2 = force.2 / self.mass;
     }
 }
@@ -0,0 +1,5 @@
+// lib.rs
+pub mod body;
+pub mod oct_tree;
+pub mod quad_tree;
+pub mod simulation;
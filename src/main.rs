@@ -1,9 +1,8 @@
-mod body;
-mod quad_tree;
-mod simulation;
+#[cfg(feature = "render")]
+mod visualization;
 
-use quad_tree::Boundary;
-use simulation::{generate_random_bodies, Simulation};
+use gravitas::quad_tree::Boundary;
+use gravitas::simulation::{generate_random_bodies, Simulation};
 
 fn main() {
     // Define the simulation boundary
@@ -21,12 +20,23 @@ fn main() {
     // Simulation parameters
     let theta = 0.5; // Barnes-Hut approximation threshold
     let time_step = 1.0; // Time step in seconds
-    let num_steps = 10; // Number of simulation steps
+    let eps = 1.0e3; // Plummer softening length
 
     // Initialize the simulation
-    let mut simulation = Simulation::new(bodies, boundary, theta, time_step);
+    let simulation = Simulation::new(bodies, boundary, theta, time_step, eps);
 
-    // Run the simulation
-    println!("Starting simulation with {} bodies...", num_bodies);
-    simulation.run(num_steps);
+    #[cfg(feature = "render")]
+    {
+        // Hand off to the interactive viewer, which drives `Simulation::update`
+        // itself once per frame.
+        visualization::run(simulation);
+    }
+
+    #[cfg(not(feature = "render"))]
+    {
+        let num_steps = 10; // Number of simulation steps
+        let mut simulation = simulation;
+        println!("Starting simulation with {} bodies...", num_bodies);
+        simulation.run(num_steps);
+    }
 }
@@ -0,0 +1,272 @@
+// oct_tree.rs
+//! 3D counterpart to `quad_tree`: an octree for Barnes-Hut force
+//! approximation in three dimensions. Real cluster-collision scenarios (two
+//! star groups passing through each other) need this, while 2D users keep
+//! using `quad_tree` unchanged.
+use crate::body::Body3;
+
+#[derive(Clone, Copy)]
+pub struct Boundary3 {
+    pub x_min: f64,
+    pub x_max: f64,
+    pub y_min: f64,
+    pub y_max: f64,
+    pub z_min: f64,
+    pub z_max: f64,
+}
+
+impl Boundary3 {
+    pub fn contains(&self, x: f64, y: f64, z: f64) -> bool {
+        x >= self.x_min
+            && x <= self.x_max
+            && y >= self.y_min
+            && y <= self.y_max
+            && z >= self.z_min
+            && z <= self.z_max
+    }
+
+    pub fn center(&self) -> (f64, f64, f64) {
+        (
+            (self.x_min + self.x_max) / 2.0,
+            (self.y_min + self.y_max) / 2.0,
+            (self.z_min + self.z_max) / 2.0,
+        )
+    }
+
+    pub fn subdivide(&self) -> [Boundary3; 8] {
+        let (x_center, y_center, z_center) = self.center();
+        let mut octants = [Boundary3 {
+            x_min: self.x_min,
+            x_max: x_center,
+            y_min: self.y_min,
+            y_max: y_center,
+            z_min: self.z_min,
+            z_max: z_center,
+        }; 8];
+
+        for (i, octant) in octants.iter_mut().enumerate() {
+            octant.x_min = if i & 1 == 0 { self.x_min } else { x_center };
+            octant.x_max = if i & 1 == 0 { x_center } else { self.x_max };
+            octant.y_min = if i & 2 == 0 { self.y_min } else { y_center };
+            octant.y_max = if i & 2 == 0 { y_center } else { self.y_max };
+            octant.z_min = if i & 4 == 0 { self.z_min } else { z_center };
+            octant.z_max = if i & 4 == 0 { z_center } else { self.z_max };
+        }
+
+        octants
+    }
+}
+
+pub enum OctTreeNode {
+    Empty,
+    Leaf {
+        body: Body3,
+    },
+    Internal {
+        center_of_mass: (f64, f64, f64),
+        total_mass: f64,
+        side_length: f64,
+        children: [Option<Box<OctTreeNode>>; 8],
+    },
+}
+
+impl OctTreeNode {
+    pub fn insert(&mut self, body: Body3, boundary: Boundary3) {
+        match self {
+            OctTreeNode::Empty => {
+                *self = OctTreeNode::Leaf { body };
+            }
+            OctTreeNode::Leaf {
+                body: existing_body,
+            } => {
+                if existing_body.position == body.position {
+                    // Avoid infinite recursion by ignoring identical positions
+                    return;
+                }
+
+                let mut children: [Option<Box<OctTreeNode>>; 8] = Default::default();
+                let octants = boundary.subdivide();
+
+                // Insert existing body
+                for (i, octant) in octants.iter().enumerate() {
+                    if octant.contains(
+                        existing_body.position.0,
+                        existing_body.position.1,
+                        existing_body.position.2,
+                    ) {
+                        children[i] = Some(Box::new(OctTreeNode::Leaf {
+                            body: existing_body.clone(),
+                        }));
+                        break;
+                    }
+                }
+
+                // Insert new body
+                for (i, octant) in octants.iter().enumerate() {
+                    if octant.contains(body.position.0, body.position.1, body.position.2) {
+                        if children[i].is_none() {
+                            children[i] = Some(Box::new(OctTreeNode::Leaf { body: body.clone() }));
+                        } else {
+                            children[i]
+                                .as_mut()
+                                .unwrap()
+                                .insert(body.clone(), *octant);
+                        }
+                        break;
+                    }
+                }
+
+                *self = OctTreeNode::Internal {
+                    center_of_mass: Self::calculate_center_of_mass(&children),
+                    total_mass: body.mass + existing_body.mass,
+                    side_length: boundary.x_max - boundary.x_min,
+                    children,
+                };
+            }
+            OctTreeNode::Internal {
+                ref mut children,
+                ref mut center_of_mass,
+                ref mut total_mass,
+                ..
+            } => {
+                let octants = boundary.subdivide();
+                for (i, octant) in octants.iter().enumerate() {
+                    if octant.contains(body.position.0, body.position.1, body.position.2) {
+                        if children[i].is_none() {
+                            children[i] = Some(Box::new(OctTreeNode::Empty));
+                        }
+                        children[i]
+                            .as_mut()
+                            .unwrap()
+                            .insert(body.clone(), *octant);
+                        break;
+                    }
+                }
+                *center_of_mass = Self::calculate_center_of_mass(children);
+                *total_mass += body.mass;
+            }
+        }
+    }
+
+    fn calculate_center_of_mass(children: &[Option<Box<OctTreeNode>>; 8]) -> (f64, f64, f64) {
+        let mut total_mass = 0.0;
+        let mut x_mass_sum = 0.0;
+        let mut y_mass_sum = 0.0;
+        let mut z_mass_sum = 0.0;
+
+        for child in children.iter() {
+            if let Some(node) = child {
+                match **node {
+                    OctTreeNode::Empty => continue,
+                    OctTreeNode::Leaf { ref body } => {
+                        total_mass += body.mass;
+                        x_mass_sum += body.position.0 * body.mass;
+                        y_mass_sum += body.position.1 * body.mass;
+                        z_mass_sum += body.position.2 * body.mass;
+                    }
+                    OctTreeNode::Internal {
+                        center_of_mass,
+                        total_mass: mass,
+                        ..
+                    } => {
+                        total_mass += mass;
+                        x_mass_sum += center_of_mass.0 * mass;
+                        y_mass_sum += center_of_mass.1 * mass;
+                        z_mass_sum += center_of_mass.2 * mass;
+                    }
+                }
+            }
+        }
+
+        if total_mass > 0.0 {
+            (
+                x_mass_sum / total_mass,
+                y_mass_sum / total_mass,
+                z_mass_sum / total_mass,
+            )
+        } else {
+            (0.0, 0.0, 0.0)
+        }
+    }
+
+    pub fn calculate_force(&self, body: &Body3, theta: f64) -> (f64, f64, f64) {
+        match self {
+            OctTreeNode::Empty => (0.0, 0.0, 0.0),
+            OctTreeNode::Leaf { body: other_body } => {
+                if body.position == other_body.position {
+                    (0.0, 0.0, 0.0)
+                } else {
+                    calculate_gravity(body, other_body.position, other_body.mass)
+                }
+            }
+            OctTreeNode::Internal {
+                center_of_mass,
+                total_mass,
+                side_length,
+                children,
+            } => {
+                let dx = center_of_mass.0 - body.position.0;
+                let dy = center_of_mass.1 - body.position.1;
+                let dz = center_of_mass.2 - body.position.2;
+                let d = (dx * dx + dy * dy + dz * dz).sqrt();
+
+                if d == 0.0 || (side_length / d) < theta {
+                    calculate_gravity(body, *center_of_mass, *total_mass)
+                } else {
+                    let mut force = (0.0, 0.0, 0.0);
+                    for child in children.iter() {
+                        if let Some(child) = child {
+                            let child_force = child.calculate_force(body, theta);
+                            force.0 += child_force.0;
+                            force.1 += child_force.1;
+                            force.2 += child_force.2;
+                        }
+                    }
+                    force
+                }
+            }
+        }
+    }
+}
+
+pub struct OctTree {
+    root: OctTreeNode,
+    boundary: Boundary3,
+}
+
+impl OctTree {
+    pub fn new(boundary: Boundary3) -> Self {
+        Self {
+            root: OctTreeNode::Empty,
+            boundary,
+        }
+    }
+
+    pub fn insert(&mut self, body: Body3) {
+        self.root.insert(body, self.boundary);
+    }
+
+    pub fn calculate_force(&self, body: &Body3, theta: f64) -> (f64, f64, f64) {
+        self.root.calculate_force(body, theta)
+    }
+
+    pub fn get_boundary(&self) -> &Boundary3 {
+        &self.boundary
+    }
+}
+
+fn calculate_gravity(body: &Body3, other_pos: (f64, f64, f64), other_mass: f64) -> (f64, f64, f64) {
+    const G: f64 = 6.67430e-11;
+    let dx = other_pos.0 - body.position.0;
+    let dy = other_pos.1 - body.position.1;
+    let dz = other_pos.2 - body.position.2;
+    let d_squared = dx * dx + dy * dy + dz * dz;
+    let d = d_squared.sqrt();
+
+    if d < 1e-10 {
+        return (0.0, 0.0, 0.0);
+    }
+
+    let force = G * body.mass * other_mass / d_squared;
+    (force * dx / d, force * dy / d, force * dz / d)
+}
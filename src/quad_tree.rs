@@ -59,6 +59,7 @@ pub enum QuadTreeNode {
     Internal {
         center_of_mass: (f64, f64),
         total_mass: f64,
+        side_length: f64,
         children: [Option<Box<QuadTreeNode>>; 4],
     },
 }
@@ -108,6 +109,7 @@ impl QuadTreeNode {
                 *self = QuadTreeNode::Internal {
                     center_of_mass: Self::calculate_center_of_mass(&children),
                     total_mass: body.mass + existing_body.mass,
+                    side_length: boundary.x_max - boundary.x_min,
                     children,
                 };
             }
@@ -170,32 +172,33 @@ impl QuadTreeNode {
         }
     }
 
-    pub fn calculate_force(&self, body: &Body, theta: f64) -> (f64, f64) {
+    pub fn calculate_force(&self, body: &Body, theta: f64, eps: f64) -> (f64, f64) {
         match self {
             QuadTreeNode::Empty => (0.0, 0.0),
             QuadTreeNode::Leaf { body: other_body } => {
                 if body.position == other_body.position {
                     (0.0, 0.0)
                 } else {
-                    calculate_gravity(body, other_body.position, other_body.mass)
+                    calculate_gravity(body, other_body.position, other_body.mass, eps)
                 }
             }
             QuadTreeNode::Internal {
                 center_of_mass,
                 total_mass,
+                side_length,
                 children,
             } => {
                 let dx = center_of_mass.0 - body.position.0;
                 let dy = center_of_mass.1 - body.position.1;
                 let d = (dx * dx + dy * dy).sqrt();
 
-                if d == 0.0 || (self.get_boundary_size() / d) < theta {
-                    calculate_gravity(body, *center_of_mass, *total_mass)
+                if d == 0.0 || (side_length / d) < theta {
+                    calculate_gravity(body, *center_of_mass, *total_mass, eps)
                 } else {
                     let mut force = (0.0, 0.0);
                     for child in children.iter() {
                         if let Some(child) = child {
-                            let child_force = child.calculate_force(body, theta);
+                            let child_force = child.calculate_force(body, theta, eps);
                             force.0 += child_force.0;
                             force.1 += child_force.1;
                         }
@@ -206,42 +209,51 @@ impl QuadTreeNode {
         }
     }
 
-    fn get_boundary_size(&self) -> f64 {
+    /// Collects every body within `radius` of `center`, pruning any quadrant
+    /// whose boundary cannot intersect the query circle.
+    fn bodies_within<'a>(
+        &'a self,
+        boundary: Boundary,
+        center: (f64, f64),
+        radius: f64,
+        out: &mut Vec<&'a Body>,
+    ) {
+        if !boundary_intersects_circle(&boundary, center, radius) {
+            return;
+        }
+
         match self {
+            QuadTreeNode::Empty => {}
+            QuadTreeNode::Leaf { body } => {
+                let dx = body.position.0 - center.0;
+                let dy = body.position.1 - center.1;
+                if dx * dx + dy * dy <= radius * radius {
+                    out.push(body);
+                }
+            }
             QuadTreeNode::Internal { children, .. } => {
-                if let Some(child) = &children[0] {
-                    let boundary = child.get_boundary();
-                    boundary.x_max - boundary.x_min
-                } else {
-                    0.0
+                let quadrants = boundary.subdivide();
+                for (child, quadrant) in children.iter().zip(quadrants.iter()) {
+                    if let Some(child) = child {
+                        child.bodies_within(*quadrant, center, radius, out);
+                    }
                 }
             }
-            _ => 0.0,
         }
     }
 
-    fn get_boundary(&self) -> Boundary {
-        match self {
-            QuadTreeNode::Internal { children, .. } => {
-                if let Some(child) = &children[0] {
-                    child.get_boundary()
-                } else {
-                    Boundary {
-                        x_min: 0.0,
-                        x_max: 0.0,
-                        y_min: 0.0,
-                        y_max: 0.0,
-                    }
+    fn collect_cell_boundaries(&self, boundary: Boundary, out: &mut Vec<Boundary>) {
+        if let QuadTreeNode::Internal { children, .. } = self {
+            out.push(boundary);
+            let quadrants = boundary.subdivide();
+            for (child, quadrant) in children.iter().zip(quadrants.iter()) {
+                if let Some(child) = child {
+                    child.collect_cell_boundaries(*quadrant, out);
                 }
             }
-            _ => Boundary {
-                x_min: 0.0,
-                x_max: 0.0,
-                y_min: 0.0,
-                y_max: 0.0,
-            },
         }
     }
+
 }
 
 pub struct QuadTree {
@@ -261,26 +273,59 @@ impl QuadTree {
         self.root.insert(body, self.boundary);
     }
 
-    pub fn calculate_force(&self, body: &Body, theta: f64) -> (f64, f64) {
-        self.root.calculate_force(body, theta)
+    pub fn calculate_force(&self, body: &Body, theta: f64, eps: f64) -> (f64, f64) {
+        self.root.calculate_force(body, theta, eps)
     }
 
     pub fn get_boundary(&self) -> &Boundary {
         &self.boundary
     }
+
+    /// Returns the boundary of every internal (subdivided) cell, for
+    /// visualizing the Barnes-Hut subdivision.
+    pub fn cell_boundaries(&self) -> Vec<Boundary> {
+        let mut cells = Vec::new();
+        self.root.collect_cell_boundaries(self.boundary, &mut cells);
+        cells
+    }
+
+    /// Returns every body within `radius` of `center`, using the tree to
+    /// prune quadrants that can't possibly intersect the query circle —
+    /// the same spatial-pruning idea as a kd-tree radius search.
+    pub fn bodies_within(&self, center: (f64, f64), radius: f64) -> Vec<&Body> {
+        let mut out = Vec::new();
+        self.root.bodies_within(self.boundary, center, radius, &mut out);
+        out
+    }
 }
 
-fn calculate_gravity(body: &Body, other_pos: (f64, f64), other_mass: f64) -> (f64, f64) {
+/// Closest-point-to-box distance check: true if the circle at `center` with
+/// `radius` can reach inside `boundary`.
+fn boundary_intersects_circle(boundary: &Boundary, center: (f64, f64), radius: f64) -> bool {
+    let closest_x = center.0.clamp(boundary.x_min, boundary.x_max);
+    let closest_y = center.1.clamp(boundary.y_min, boundary.y_max);
+    let dx = center.0 - closest_x;
+    let dy = center.1 - closest_y;
+    dx * dx + dy * dy <= radius * radius
+}
+
+// `QuadTreeNode` holds only plain f64s, `Body`s, and boxed children, so it is
+// `Sync` automatically; `Simulation::update` relies on that to share a `&QuadTree`
+// across a rayon `par_iter` force evaluation.
+const _: fn() = || {
+    fn assert_sync<T: Sync>() {}
+    assert_sync::<QuadTreeNode>();
+};
+
+/// Plummer-softened gravity: `eps` bounds the force smoothly as `d` shrinks,
+/// instead of the old hard cutoff that discarded close encounters outright.
+fn calculate_gravity(body: &Body, other_pos: (f64, f64), other_mass: f64, eps: f64) -> (f64, f64) {
     const G: f64 = 6.67430e-11;
     let dx = other_pos.0 - body.position.0;
     let dy = other_pos.1 - body.position.1;
-    let d_squared = dx * dx + dy * dy;
-    let d = d_squared.sqrt();
-
-    if d < 1e-10 {
-        return (0.0, 0.0);
-    }
+    let d_softened_squared = dx * dx + dy * dy + eps * eps;
+    let d_softened = d_softened_squared.sqrt();
 
-    let force = G * body.mass * other_mass / d_squared;
-    (force * dx / d, force * dy / d)
+    let force = G * body.mass * other_mass / d_softened_squared;
+    (force * dx / d_softened, force * dy / d_softened)
 }
@@ -1,5 +1,7 @@
 use crate::body::Body;
 use crate::quad_tree::{Boundary, QuadTree};
+use rayon::prelude::*;
+use std::collections::HashMap;
 use std::f64::consts::PI;
 
 pub struct Simulation {
@@ -7,42 +9,153 @@ pub struct Simulation {
     pub quad_tree: QuadTree,
     pub theta: f64,     // Threshold for Barnes-Hut approximation
     pub time_step: f64, // Time step for the simulation
+    pub eps: f64,       // Plummer softening length
+    // Whether `body.acceleration` is out of date for the current positions. The
+    // final half-kick of a step already leaves this valid for the next
+    // step's leading kick, so it's only true initially and after a merge
+    // changes the body set out from under the stored accelerations.
+    accelerations_stale: bool,
 }
 
 impl Simulation {
-    pub fn new(bodies: Vec<Body>, boundary: Boundary, theta: f64, time_step: f64) -> Self {
+    pub fn new(bodies: Vec<Body>, boundary: Boundary, theta: f64, time_step: f64, eps: f64) -> Self {
         let quad_tree = QuadTree::new(boundary);
         Self {
             bodies,
             quad_tree,
             theta,
             time_step,
+            eps,
+            accelerations_stale: true,
         }
     }
 
+    /// Advances the simulation by one step using kick-drift-kick velocity
+    /// Verlet: half-kick with the current acceleration, drift positions by a
+    /// full `time_step`, recompute accelerations at the new positions, then
+    /// half-kick again. This keeps total energy bounded over long runs,
+    /// unlike the split-Euler scheme it replaced.
     pub fn update(&mut self) {
-        // Clear and rebuild the quadtree
+        if self.accelerations_stale {
+            self.rebuild_tree();
+            self.recompute_accelerations();
+            self.accelerations_stale = false;
+        }
+
+        for body in &mut self.bodies {
+            body.half_kick(self.time_step);
+        }
+
+        for body in &mut self.bodies {
+            body.drift(self.time_step);
+        }
+
+        self.rebuild_tree();
+        self.recompute_accelerations();
+        for body in &mut self.bodies {
+            body.half_kick(self.time_step);
+        }
+
+        // Merge overlapping bodies while the tree still matches their current
+        // positions, before the next step moves anything. A merged body is
+        // built fresh by `Body::new` with zero acceleration, so force a
+        // rebuild before the next step's leading kick.
+        let count_before_merge = self.bodies.len();
+        self.resolve_collisions();
+        if self.bodies.len() != count_before_merge {
+            self.accelerations_stale = true;
+        }
+    }
+
+    fn rebuild_tree(&mut self) {
         self.quad_tree = QuadTree::new(self.quad_tree.get_boundary().clone());
         for body in &self.bodies {
             self.quad_tree.insert(body.clone());
         }
+    }
 
-        // Reset accelerations
+    /// The tree is immutable once built, so force evaluation is embarrassingly
+    /// parallel: compute every force concurrently, then apply them in a
+    /// second, serial pass.
+    fn recompute_accelerations(&mut self) {
         for body in &mut self.bodies {
             body.acceleration = (0.0, 0.0);
         }
 
-        // Calculate forces and update bodies
-        for body in &mut self.bodies {
-            let force = self.quad_tree.calculate_force(body, self.theta);
+        let forces: Vec<(f64, f64)> = self
+            .bodies
+            .par_iter()
+            .map(|body| self.quad_tree.calculate_force(body, self.theta, self.eps))
+            .collect();
+        for (body, force) in self.bodies.iter_mut().zip(forces) {
             body.apply_force(force);
         }
+    }
 
-        // Update positions and velocities
-        for body in &mut self.bodies {
-            body.update_velocity(self.time_step);
-            body.update_position(self.time_step);
+    /// Merges any bodies that overlap (distance <= sum of their `_radius`)
+    /// into one, conserving mass and momentum. Close encounters would
+    /// otherwise produce singular forces and nonsense trajectories.
+    fn resolve_collisions(&mut self) {
+        let n = self.bodies.len();
+        let mut merged = vec![false; n];
+        let max_radius = self.bodies.iter().map(|b| b._radius).fold(0.0, f64::max);
+
+        // Looking up a neighbor's index by scanning `self.bodies` would make
+        // this whole pass O(n^2) and regress on duplicate positions; map each
+        // body's stable id to its index instead, built once up front.
+        let index_by_id: HashMap<u64, usize> =
+            self.bodies.iter().enumerate().map(|(i, b)| (b.id, i)).collect();
+
+        for i in 0..n {
+            if merged[i] {
+                continue;
+            }
+            let probe = self.bodies[i].clone();
+            let search_radius = probe._radius + max_radius;
+
+            for neighbor in self.quad_tree.bodies_within(probe.position, search_radius) {
+                if neighbor.id == probe.id {
+                    continue; // the body querying itself
+                }
+                let Some(&j) = index_by_id.get(&neighbor.id) else {
+                    continue;
+                };
+                if merged[j] {
+                    continue;
+                }
+
+                let collision_distance = probe._radius + neighbor._radius;
+                let dx = probe.position.0 - neighbor.position.0;
+                let dy = probe.position.1 - neighbor.position.1;
+                if dx * dx + dy * dy <= collision_distance * collision_distance {
+                    self.bodies[i] = Self::merge(&self.bodies[i], &self.bodies[j]);
+                    merged[j] = true;
+                }
+            }
         }
+
+        let mut kept = Vec::with_capacity(n);
+        for (i, body) in std::mem::take(&mut self.bodies).into_iter().enumerate() {
+            if !merged[i] {
+                kept.push(body);
+            }
+        }
+        self.bodies = kept;
+    }
+
+    /// Inelastically merges two bodies, conserving mass and momentum.
+    fn merge(a: &Body, b: &Body) -> Body {
+        let mass = a.mass + b.mass;
+        let position = (
+            (a.position.0 * a.mass + b.position.0 * b.mass) / mass,
+            (a.position.1 * a.mass + b.position.1 * b.mass) / mass,
+        );
+        let velocity = (
+            (a.velocity.0 * a.mass + b.velocity.0 * b.mass) / mass,
+            (a.velocity.1 * a.mass + b.velocity.1 * b.mass) / mass,
+        );
+        let _radius = (mass / (4.0 / 3.0 * PI)).cbrt();
+        Body::new(position, velocity, mass, _radius)
     }
 
     pub fn run(&mut self, steps: usize) {
@@ -79,3 +192,98 @@ pub fn generate_random_bodies(count: usize, boundary: &Boundary) -> Vec<Body> {
     }
     bodies
 }
+
+const G: f64 = 6.67430e-11;
+
+/// Generates a gravitationally-bound cluster: bodies are centrally
+/// concentrated around `center` out to `radius`, each on a circular orbit
+/// about the cluster's center of mass (speed ~= sqrt(G * M_enclosed / r)),
+/// plus an overall `bulk_velocity` drift.
+pub fn generate_cluster(
+    center: (f64, f64),
+    radius: f64,
+    count: usize,
+    bulk_velocity: (f64, f64),
+) -> Vec<Body> {
+    struct Seed {
+        offset: (f64, f64),
+        mass: f64,
+    }
+
+    let mut seeds: Vec<Seed> = (0..count)
+        .map(|_| {
+            // Bias `r` toward the center so the cloud is denser in the middle
+            // than a uniform disk would be.
+            let u: f64 = rand::random();
+            let r = radius * u.powf(2.0);
+            let angle = rand::random::<f64>() * 2.0 * PI;
+            let mass = rand::random::<f64>() * 1e5 + 1e3;
+            Seed {
+                offset: (r * angle.cos(), r * angle.sin()),
+                mass,
+            }
+        })
+        .collect();
+
+    // Offsets are relative to `center`; orbits are set up about the seeds'
+    // mass-weighted center, which a random sample only places near `center`.
+    let seed_mass: f64 = seeds.iter().map(|s| s.mass).sum();
+    let com = (
+        seeds.iter().map(|s| s.offset.0 * s.mass).sum::<f64>() / seed_mass,
+        seeds.iter().map(|s| s.offset.1 * s.mass).sum::<f64>() / seed_mass,
+    );
+
+    // Sort by distance from the center of mass so the mass enclosed within
+    // each body's orbit can be accumulated in a single pass.
+    seeds.sort_by(|a, b| {
+        let ra = (a.offset.0 - com.0).hypot(a.offset.1 - com.1);
+        let rb = (b.offset.0 - com.0).hypot(b.offset.1 - com.1);
+        ra.partial_cmp(&rb).unwrap()
+    });
+
+    let mut bodies = Vec::with_capacity(count);
+    let mut enclosed_mass = 0.0;
+    for seed in seeds {
+        enclosed_mass += seed.mass;
+        let (ox, oy) = seed.offset;
+        let (rx, ry) = (ox - com.0, oy - com.1);
+        let r = rx.hypot(ry).max(1.0);
+        let speed = (G * enclosed_mass / r).sqrt();
+
+        // Tangential direction: rotate the radial offset by 90 degrees.
+        let tangent = (-ry / r, rx / r);
+        let velocity = (
+            tangent.0 * speed + bulk_velocity.0,
+            tangent.1 * speed + bulk_velocity.1,
+        );
+        let position = (center.0 + ox, center.1 + oy);
+        let _radius = (seed.mass / (4.0 / 3.0 * PI)).cbrt();
+        bodies.push(Body::new(position, velocity, seed.mass, _radius));
+    }
+    bodies
+}
+
+/// Spawns two clusters of `count_per_cluster` bodies each on a collision
+/// course inside `boundary` — the canonical N-body test case of two star
+/// groups merging, instead of a featureless random scatter.
+pub fn two_cluster_collision(boundary: &Boundary, count_per_cluster: usize) -> Vec<Body> {
+    let extent = (boundary.x_max - boundary.x_min).min(boundary.y_max - boundary.y_min);
+    let cluster_radius = extent * 0.1;
+    let separation = extent * 0.3;
+    let (center_x, center_y) = boundary.center();
+    let closing_speed = separation * 1.0e-4;
+
+    let mut bodies = generate_cluster(
+        (center_x - separation, center_y),
+        cluster_radius,
+        count_per_cluster,
+        (closing_speed, 0.0),
+    );
+    bodies.extend(generate_cluster(
+        (center_x + separation, center_y),
+        cluster_radius,
+        count_per_cluster,
+        (-closing_speed, 0.0),
+    ));
+    bodies
+}
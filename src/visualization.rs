@@ -0,0 +1,128 @@
+// visualization.rs
+//! Optional real-time viewer for the simulation, built on Bevy.
+//!
+//! Enabled with the `render` feature; without it this module is not compiled
+//! and the crate falls back to the plain text summary in `simulation.rs`.
+use bevy::input::mouse::{MouseMotion, MouseWheel};
+use bevy::prelude::*;
+use bevy::sprite::MaterialMesh2dBundle;
+
+use gravitas::quad_tree::Boundary;
+use gravitas::simulation::Simulation;
+
+/// World units per pixel at the starting zoom level. The simulation boundary
+/// spans roughly 2e6 units, so we start zoomed far out and let the user
+/// scroll in.
+const INITIAL_CAMERA_SCALE: f32 = 4_000.0;
+
+#[derive(Resource)]
+struct SimState(Simulation);
+
+/// The mesh/material shared by every body entity, cached once so
+/// `sync_bodies` isn't allocating new assets every frame.
+#[derive(Resource)]
+struct BodyVisuals {
+    mesh: Handle<Mesh>,
+    material: Handle<ColorMaterial>,
+}
+
+/// Marks an entity as a rendered body, so `sync_bodies` can find and despawn
+/// the previous frame's entities. Bodies can merge away during
+/// `Simulation::update` (chunk0-5's collision pass), so the body count
+/// changes frame to frame — entities can't be kept alive by a fixed index.
+#[derive(Component)]
+struct BodyMarker;
+
+/// Runs an interactive N-body explorer for `simulation`, taking over the
+/// simulation loop (this call does not return until the window is closed).
+pub fn run(simulation: Simulation) {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .insert_resource(SimState(simulation))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (step_simulation, sync_bodies).chain())
+        .add_systems(Update, (pan_zoom_camera, draw_quad_tree))
+        .run();
+}
+
+fn setup(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>, mut materials: ResMut<Assets<ColorMaterial>>) {
+    let mut camera = Camera2dBundle::default();
+    camera.projection.scale = INITIAL_CAMERA_SCALE;
+    commands.spawn(camera);
+
+    commands.insert_resource(BodyVisuals {
+        mesh: meshes.add(Circle::new(1.0)),
+        material: materials.add(Color::srgb(0.9, 0.9, 1.0)),
+    });
+}
+
+fn step_simulation(mut sim: ResMut<SimState>) {
+    sim.0.update();
+}
+
+/// Despawns last frame's body entities and respawns one per current body,
+/// since merges can shrink `sim.0.bodies` between frames.
+fn sync_bodies(
+    mut commands: Commands,
+    sim: Res<SimState>,
+    visuals: Res<BodyVisuals>,
+    rendered: Query<Entity, With<BodyMarker>>,
+) {
+    for entity in &rendered {
+        commands.entity(entity).despawn();
+    }
+
+    for body in &sim.0.bodies {
+        commands.spawn((
+            MaterialMesh2dBundle {
+                mesh: visuals.mesh.clone().into(),
+                material: visuals.material.clone(),
+                transform: Transform::from_xyz(body.position.0 as f32, body.position.1 as f32, 0.0)
+                    .with_scale(Vec3::splat(body._radius as f32)),
+                ..default()
+            },
+            BodyMarker,
+        ));
+    }
+}
+
+/// Scroll to zoom, drag with the middle mouse button to pan, so the full
+/// +-1e6 boundary can be scrubbed without rebuilding the app.
+fn pan_zoom_camera(
+    mut wheel_events: EventReader<MouseWheel>,
+    mut motion_events: EventReader<MouseMotion>,
+    buttons: Res<ButtonInput<MouseButton>>,
+    mut camera: Query<(&mut Transform, &mut OrthographicProjection), With<Camera2d>>,
+) {
+    let Ok((mut transform, mut projection)) = camera.get_single_mut() else {
+        return;
+    };
+
+    for event in wheel_events.read() {
+        let zoom_factor = 1.0 - event.y * 0.1;
+        projection.scale = (projection.scale * zoom_factor).clamp(1.0, 1.0e5);
+    }
+
+    if buttons.pressed(MouseButton::Middle) {
+        for event in motion_events.read() {
+            transform.translation.x -= event.delta.x * projection.scale;
+            transform.translation.y += event.delta.y * projection.scale;
+        }
+    }
+}
+
+/// Draws the current quad-tree cell boundaries so the Barnes-Hut subdivision
+/// is visible alongside the bodies.
+fn draw_quad_tree(sim: Res<SimState>, mut gizmos: Gizmos) {
+    for cell in sim.0.quad_tree.cell_boundaries() {
+        draw_cell(&mut gizmos, &cell);
+    }
+}
+
+fn draw_cell(gizmos: &mut Gizmos, cell: &Boundary) {
+    let min = Vec2::new(cell.x_min as f32, cell.y_min as f32);
+    let max = Vec2::new(cell.x_max as f32, cell.y_max as f32);
+    let center = (min + max) / 2.0;
+    let size = max - min;
+    gizmos.rect_2d(center, 0.0, size, Color::srgba(0.3, 0.9, 0.4, 0.35));
+}